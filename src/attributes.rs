@@ -1,6 +1,18 @@
+use std::iter::FromIterator;
+
 /// Low-level trait for attributes. Used for some type magic.
 pub trait Base {
-    fn to_string(&self) -> String;
+    /// Writes this attribute's HTML representation into `f`, without
+    /// buffering an intermediate `String`.
+    fn render(&self, f: &mut dyn std::fmt::Write) -> std::fmt::Result;
+
+    /// Renders this attribute to a freshly allocated `String`. A thin
+    /// wrapper over `render`, kept for convenience.
+    fn to_string(&self) -> String {
+        let mut buf = String::new();
+        self.render(&mut buf).expect("writing to a String cannot fail");
+        buf
+    }
 }
 
 /// Common format for a tag, implemented attributes should implement this trait.
@@ -10,12 +22,28 @@ pub trait Attribute {
     fn value(&self) -> String;
 }
 
+/// Escapes the characters that would otherwise break out of an attribute
+/// value delimited by double quotes.
+fn escape(input: &str) -> String {
+    input
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
 impl<T: ?Sized> Base for T
 where
     T: Attribute,
 {
-    fn to_string(&self) -> String {
-        format!(r#"{}="{}""#, Self::ATTRIBUTE_NAME, self.value())
+    fn render(&self, f: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        write!(
+            f,
+            r#"{}="{}""#,
+            Self::ATTRIBUTE_NAME,
+            escape(&self.value())
+        )
     }
 }
 
@@ -26,14 +54,62 @@ pub trait None: Base {}
 /// The most basic attributes that most HTML elements share.
 pub trait DefaultAttribute: Base {}
 
+/// An ordered, de-duplicated set of whitespace-separated tokens, as used by
+/// the `class` attribute. Tokens keep the order they were first inserted in;
+/// inserting a token that's already present is a no-op.
+#[derive(Default)]
+pub struct SpacedSet(Vec<String>);
+
+impl SpacedSet {
+    fn insert(&mut self, token: impl Into<String>) {
+        let token = token.into();
+
+        if !self.0.iter().any(|existing| existing == &token) {
+            self.0.push(token);
+        }
+    }
+}
+
+impl std::fmt::Display for SpacedSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.0.join(" "))
+    }
+}
+
+impl From<&str> for SpacedSet {
+    fn from(value: &str) -> Self {
+        value.split_whitespace().collect()
+    }
+}
+
+impl<const N: usize> From<[&str; N]> for SpacedSet {
+    fn from(value: [&str; N]) -> Self {
+        value.iter().copied().collect()
+    }
+}
+
+impl<'a> FromIterator<&'a str> for SpacedSet {
+    fn from_iter<I: IntoIterator<Item = &'a str>>(iter: I) -> Self {
+        let mut set = SpacedSet::default();
+
+        for token in iter {
+            set.insert(token);
+        }
+
+        set
+    }
+}
+
 pub mod class {
-    pub struct Attribute(pub String);
+    use super::SpacedSet;
+
+    pub struct Attribute(pub SpacedSet);
 
     impl super::Attribute for Attribute {
         const ATTRIBUTE_NAME: &'static str = "class";
 
         fn value(&self) -> String {
-            self.0.clone()
+            self.0.to_string()
         }
     }
 }
@@ -62,12 +138,19 @@ pub mod data {
     pub struct Attribute(pub HashMap<String, String>);
 
     impl super::Base for Attribute {
-        fn to_string(&self) -> String {
-            self.0
-                .iter()
-                .map(|(ref name, ref value)| format!(r#"data-{}="{}""#, name, value))
-                .collect::<Vec<String>>()
-                .join(" ")
+        fn render(&self, f: &mut dyn std::fmt::Write) -> std::fmt::Result {
+            let mut first = true;
+
+            for (name, value) in &self.0 {
+                if !first {
+                    write!(f, " ")?;
+                }
+                first = false;
+
+                write!(f, r#"data-{}="{}""#, name, super::escape(value))?;
+            }
+
+            Ok(())
         }
     }
 }
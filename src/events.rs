@@ -0,0 +1,87 @@
+//! Event handlers that can be attached to tags.
+//!
+//! Which type a handler actually is depends on the tag's output type: a
+//! `String`/server target has nothing to run a handler with, while a future
+//! wasm target could store a boxed closure instead. See
+//! [`crate::tags::OutputType`].
+
+use crate::tags::OutputType;
+
+/// The DOM events a tag can carry a handler for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    Blur,
+    Change,
+    Click,
+    Drag,
+    DragEnd,
+    DragEnter,
+    DragLeave,
+    DragOver,
+    DragStart,
+    Drop,
+    Focus,
+    Input,
+    KeyDown,
+    KeyUp,
+    MouseDown,
+    MouseOver,
+    Resize,
+    Scroll,
+    Submit,
+}
+
+impl Event {
+    /// The lowercase event name as used by the DOM (e.g. `"keydown"`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Event::Blur => "blur",
+            Event::Change => "change",
+            Event::Click => "click",
+            Event::Drag => "drag",
+            Event::DragEnd => "dragend",
+            Event::DragEnter => "dragenter",
+            Event::DragLeave => "dragleave",
+            Event::DragOver => "dragover",
+            Event::DragStart => "dragstart",
+            Event::Drop => "drop",
+            Event::Focus => "focus",
+            Event::Input => "input",
+            Event::KeyDown => "keydown",
+            Event::KeyUp => "keyup",
+            Event::MouseDown => "mousedown",
+            Event::MouseOver => "mouseover",
+            Event::Resize => "resize",
+            Event::Scroll => "scroll",
+            Event::Submit => "submit",
+        }
+    }
+}
+
+/// The event handlers attached to a single tag, keyed by [`Event`].
+///
+/// The handler type is determined by the tag's output type `T`: for
+/// `T = String`, `T::Handler` is `()`, so no meaningful handler can be
+/// attached and the rendering path simply has nothing to call.
+pub struct Events<T: OutputType>(Vec<(Event, T::Handler)>);
+
+impl<T: OutputType> Default for Events<T> {
+    fn default() -> Self {
+        Events(Vec::new())
+    }
+}
+
+impl<T: OutputType> Events<T> {
+    /// Attaches a handler for `event`, replacing any handler previously set
+    /// for the same event.
+    pub fn on(mut self, event: Event, handler: T::Handler) -> Self {
+        self.0.retain(|(existing, _)| *existing != event);
+        self.0.push((event, handler));
+        self
+    }
+
+    /// The handlers currently attached, in the order they were set.
+    pub fn handlers(&self) -> &[(Event, T::Handler)] {
+        &self.0
+    }
+}
@@ -1,4 +1,5 @@
 pub mod attributes;
+pub mod events;
 pub mod tags;
 
 /// Creates the given node. This macro tries to make a sensible way of writing HTML in rust, without resorting to compiler plugins.
@@ -8,20 +9,29 @@ pub mod tags;
 /// ```
 /// # use dolmen::{node, tags::{self, Base}};
 /// # assert_eq!(
-/// node!(div) // => <div />
-/// # .to_string(), "<div />");
+/// node!(div) // => <div></div>
+/// # .to_string(), "<div></div>");
 /// ````
 ///
 /// You can replace `div` by any other HTML node.
 ///
+/// Void elements (`br`, `img`, ...) can never have children and are always rendered
+/// in self-closing form:
+/// ```
+/// # use dolmen::{node, tags::{self, Base}};
+/// # assert_eq!(
+/// node!(br) // => <br />
+/// # .to_string(), "<br />");
+/// ```
+///
 /// When you want to add children to a node, you put them into curled braces, separated by commas like in a list:attributes
 /// ```
 /// # use dolmen::{node, tags::{self, Base}};
 /// # assert_eq!(
-/// node!(div { node!(span), node!(span) }) // => <div><span /><span /></div>
-/// # .to_string(), "<div><span /><span /></div>");
+/// node!(div { node!(span), node!(span) }) // => <div><span></span><span></span></div>
+/// # .to_string(), "<div><span></span><span></span></div>");
 /// ```
-///  
+///
 /// For inserting text, you can use the `text!` macro:
 /// ```
 /// # use dolmen::{node, text, tags::{self, Base}};
@@ -41,38 +51,89 @@ pub mod tags;
 ///         node!(div)
 ///     }
 /// })
-/// # .to_string(), "<div><span /></div>");
+/// # .to_string(), "<div><span></span></div>");
 /// ```
 ///
 /// To add attributes, use the following syntax:
 /// ```
 /// # use dolmen::{node, tags::{self, Base}, attributes};
 /// # assert_eq!(
-/// node!(div(class: "demo", id: "bar")) // => <div class="demo" id="bar" />
-/// # .to_string(), r#"<div class="demo" id="bar" />"#);
+/// node!(div(class: "demo", id: "bar")) // => <div class="demo" id="bar"></div>
+/// # .to_string(), r#"<div class="demo" id="bar"></div>"#);
 /// ```
 ///
 /// Again, the macro awaits expressions on the right side.
 ///
+/// `class` is backed by a `SpacedSet`, so it can be built from a single
+/// whitespace-separated string or from an array of tokens, de-duplicating
+/// repeats either way:
+/// ```
+/// # use dolmen::{node, tags::{self, Base}, attributes};
+/// # assert_eq!(
+/// node!(div(class: ["foo", "bar", "foo"])) // => <div class="foo bar"></div>
+/// # .to_string(), r#"<div class="foo bar"></div>"#);
+/// ```
+///
 /// You also have the special `data!` macro to set `data-*` attributes:
 /// ```
 /// # use dolmen::{node, tags::{self, Base}, attributes, data};
 /// # assert_eq!(
-/// node!(div(data: data!(foo: "bar"))) // => <div data-foo="bar" />
-/// # .to_string(), r#"<div data-foo="bar" />"#);
+/// node!(div(data: data!(foo: "bar"))) // => <div data-foo="bar"></div>
+/// # .to_string(), r#"<div data-foo="bar"></div>"#);
+/// ```
+///
+/// Nodes implement `Display`, so they can be formatted straight into a
+/// `String`, a file or a socket with `write!`. `render` writes a node into
+/// an existing `fmt::Write` buffer such as a `String`, and `to_string()` is
+/// a thin wrapper over it:
+/// ```
+/// # use dolmen::tags::{self, Base};
+/// let node = tags::div::Element::<String> {
+///     children: Vec::default(),
+///     attributes: Vec::default(),
+///     events: Default::default(),
+/// };
+/// let mut buf = String::new();
+/// node.render(&mut buf).unwrap();
+/// assert_eq!(buf, "<div></div>");
+/// ```
+///
+/// Structural elements with required named children, like `html` and
+/// `head`, are given their children as `name: expr` pairs instead, so the
+/// compiler checks each one is present and of the right tag. `head` also
+/// takes a `metadata` field: an open list of anything implementing
+/// `MetadataContent` (`meta`, `link`, `style`, `script`, ...):
+/// ```
+/// # use dolmen::{node, text, tags::{self, Base}, attributes};
+/// # assert_eq!(
+/// node!(html {
+///     head: node!(head {
+///         title: node!(title { text!("Demo") }),
+///         metadata: vec![node!(link(id: "stylesheet"))]
+///     }),
+///     body: node!(body)
+/// })
+/// // => <html><head><link id="stylesheet" /><title>Demo</title></head><body></body></html>
+/// # .to_string(), "<html><head><link id=\"stylesheet\" /><title>Demo</title></head><body></body></html>");
 #[macro_export]
 macro_rules! node {
+    ($tag:ident { $( $field:ident : $child:expr ),+ }) => {
+        Box::new(tags::$tag::Element::<String> { $( $field: $child ),*, attributes: Vec::default(), events: Default::default() })
+    };
+    ($tag:ident ( $( $name:ident : $value:expr ),+ ) { $( $field:ident : $child:expr ),+ }) => {
+        Box::new(tags::$tag::Element::<String> { $( $field: $child ),*, attributes: vec![$( Box::new(attributes::$name::Attribute($value.into())) ),*], events: Default::default() })
+    };
     ($tag:ident) => {
-        Box::new(tags::$tag::Element { children: Vec::default(), attributes: Vec::default() })
+        Box::new(tags::$tag::Element::<String> { children: Vec::default(), attributes: Vec::default(), events: Default::default() })
     };
     ($tag:ident { $( $child:expr ),+ }) => {
-        Box::new(tags::$tag::Element { children: vec![ $( $child ),* ], attributes: Vec::default() })
+        Box::new(tags::$tag::Element::<String> { children: vec![ $( $child ),* ], attributes: Vec::default(), events: Default::default() })
     };
     ($tag:ident ( $( $name:ident : $value:expr ),+ )) => {
-        Box::new(tags::$tag::Element { children: Vec::default(), attributes: vec![$( Box::new(attributes::$name::Attribute($value.into())) ),*] })
+        Box::new(tags::$tag::Element::<String> { children: Vec::default(), attributes: vec![$( Box::new(attributes::$name::Attribute($value.into())) ),*], events: Default::default() })
     };
     ($tag:ident ( $( $name:ident : $value:expr ),+ ) { $( $child:expr ),+ }) => {
-        Box::new(tags::$tag::Element { children: vec![ $( $child ),* ], attributes: vec![$( Box::new(attributes::$name::Attribute($value.into())) ),*] })
+        Box::new(tags::$tag::Element::<String> { children: vec![ $( $child ),* ], attributes: vec![$( Box::new(attributes::$name::Attribute($value.into())) ),*], events: Default::default() })
     };
 }
 
@@ -101,12 +162,13 @@ macro_rules! data {
 mod tests {
     use crate::{
         attributes,
-        tags::{self, Base},
+        events::Event,
+        tags::{self, Base, Tag},
     };
 
     #[test]
     fn test_simple_tag() {
-        assert_eq!(node!(div).to_string(), "<div />");
+        assert_eq!(node!(div).to_string(), "<div></div>");
     }
 
     #[test]
@@ -119,14 +181,14 @@ mod tests {
 
     #[test]
     fn test_tag_with_id() {
-        assert_eq!(node!(div(id: "foo")).to_string(), r#"<div id="foo" />"#);
+        assert_eq!(node!(div(id: "foo")).to_string(), r#"<div id="foo"></div>"#);
     }
 
     #[test]
     fn test_tag_with_children() {
         assert_eq!(
             node!(div { node!(span), node!(span) }).to_string(),
-            "<div><span /><span /></div>"
+            "<div><span></span><span></span></div>"
         );
     }
 
@@ -134,7 +196,62 @@ mod tests {
     fn test_data() {
         assert_eq!(
             node!(div(data: data!(foo: "bar"))).to_string(),
-            r#"<div data-foo="bar" />"#
+            r#"<div data-foo="bar"></div>"#
+        );
+    }
+
+    #[test]
+    fn test_class_from_str() {
+        assert_eq!(
+            node!(div(class: "foo bar")).to_string(),
+            r#"<div class="foo bar"></div>"#
+        );
+    }
+
+    #[test]
+    fn test_class_from_array_dedups() {
+        assert_eq!(
+            node!(div(class: ["foo", "bar", "foo", "baz"])).to_string(),
+            r#"<div class="foo bar baz"></div>"#
+        );
+    }
+
+    #[test]
+    fn test_void_tag_is_self_closing() {
+        assert_eq!(node!(br).to_string(), "<br />");
+    }
+
+    #[test]
+    fn test_events_can_be_attached() {
+        let mut button = node!(button);
+        button.events = button.events.on(Event::Click, ());
+
+        assert_eq!(button.events().handlers(), &[(Event::Click, ())]);
+        // attaching handlers does not change how a `String`-rendered tag looks
+        assert_eq!(button.to_string(), "<button></button>");
+    }
+
+    #[test]
+    fn test_text_is_escaped() {
+        assert_eq!(
+            node!(div { text!("a < b & \"c\"") }).to_string(),
+            "<div>a &lt; b &amp; \"c\"</div>"
+        );
+    }
+
+    #[test]
+    fn test_attribute_is_escaped() {
+        assert_eq!(
+            node!(div(id: "\"foo\" & 'bar'")).to_string(),
+            r#"<div id="&quot;foo&quot; &amp; &#39;bar&#39;"></div>"#
+        );
+    }
+
+    #[test]
+    fn test_raw_text_is_not_escaped() {
+        assert_eq!(
+            node!(div { Box::new(tags::RawText("<b>hi</b>".into())) }).to_string(),
+            "<div><b>hi</b></div>"
         );
     }
 
@@ -145,8 +262,59 @@ mod tests {
     #[test]
     fn test_component() {
         assert_eq!(
-            node!(span { component("Hello!") }).to_string(),
-            r#"<span><div class="component">Hello!</div></span>"#
+            node!(div { component("Hello!") }).to_string(),
+            r#"<div><div class="component">Hello!</div></div>"#
+        );
+    }
+
+    #[test]
+    fn test_list_only_accepts_list_items() {
+        assert_eq!(
+            node!(ul { node!(li { text!("one") }), node!(li { text!("two") }) }).to_string(),
+            "<ul><li>one</li><li>two</li></ul>"
+        );
+    }
+
+    #[test]
+    fn test_render_writes_into_an_existing_buffer() {
+        let mut buf = String::from("prefix:");
+        let node = tags::div::Element::<String> {
+            children: vec![text!("hi")],
+            attributes: Vec::default(),
+            events: Default::default(),
+        };
+        node.render(&mut buf).unwrap();
+
+        assert_eq!(buf, "prefix:<div>hi</div>");
+    }
+
+    #[test]
+    fn test_document_structure() {
+        assert_eq!(
+            node!(html {
+                head: node!(head {
+                    title: node!(title { text!("Demo") }),
+                    metadata: Vec::new()
+                }),
+                body: node!(body { node!(p { text!("Hello!") }) })
+            })
+            .to_string(),
+            "<html><head><title>Demo</title></head><body><p>Hello!</p></body></html>"
+        );
+    }
+
+    #[test]
+    fn test_head_accepts_open_metadata_content() {
+        assert_eq!(
+            node!(head {
+                title: node!(title { text!("Demo") }),
+                metadata: vec![
+                    node!(link(id: "stylesheet")),
+                    node!(meta(id: "charset"))
+                ]
+            })
+            .to_string(),
+            r#"<head><link id="stylesheet" /><meta id="charset" /><title>Demo</title></head>"#
         );
     }
 }
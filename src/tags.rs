@@ -1,6 +1,32 @@
+use std::fmt;
+
 /// Low-level trait for tags. Used for some type magic.
+///
+/// `to_string()` is deliberately not a method here: every implementor also
+/// implements `Display`, so `ToString::to_string` (from the standard
+/// library's blanket `Display` impl) already gives callers the old
+/// allocating API as a thin wrapper over `render`.
 pub trait Base {
-    fn to_string(&self) -> String;
+    /// Writes this node's HTML representation into `f`, without buffering
+    /// an intermediate `String` per node.
+    fn render(&self, f: &mut dyn fmt::Write) -> fmt::Result;
+}
+
+/// Marker for a tag's rendering target, determining what a tag's event
+/// handlers actually are.
+///
+/// `String` (plain server-side rendering) has nothing to run a handler
+/// with, so its `Handler` is `()`; a future wasm target could implement
+/// this for its own output type with `Handler` set to a boxed closure.
+/// Because `Handler` is tied to the output type, handlers for one output
+/// type can never be attached to an element of another.
+pub trait OutputType {
+    /// The type used to store an event handler for this output type.
+    type Handler;
+}
+
+impl OutputType for String {
+    type Handler = ();
 }
 
 /// Common format for a tag, implemented tags should implement this trait.
@@ -12,15 +38,24 @@ pub trait Tag: Base {
     /// The tag name as it will be shown in HTML
     const TAG_NAME: &'static str;
 
+    /// Whether this tag is a void element (e.g. `br`, `img`), which is
+    /// always rendered in self-closing form and can never have children.
+    /// Defaults to `false`; only set by `make_void_tag!`.
+    const VOID: bool = false;
+
     /// Trait to be implemented by the valid children of this node
     type Child: ?Sized;
     /// Trait to be implemented by the valid attributes of this node
     type Attribute: ?Sized;
+    /// The rendering target this tag carries event handlers for
+    type Output: OutputType;
 
     /// Returns the current attributes of the node
     fn attributes(&self) -> &Vec<Box<Self::Attribute>>;
     /// Returns the current children of the node
     fn children(&self) -> &Vec<Box<Self::Child>>;
+    /// Returns the event handlers attached to the node
+    fn events(&self) -> &crate::events::Events<Self::Output>;
 }
 
 /// Declare a new tag module and implements it as a standard tag.
@@ -32,16 +67,18 @@ macro_rules! make_tag {
         /// The module for the tag, its name is the one you use with the `node!` macro.
         pub mod $name {
             /// The element representation
-            pub struct Element {
+            pub struct Element<O: super::OutputType = String> {
                 pub children: Vec<Box<super::$children>>,
                 pub attributes: Vec<Box<super::$attributes>>,
+                pub events: crate::events::Events<O>,
             }
 
-            impl super::Tag for Element {
+            impl<O: super::OutputType> super::Tag for Element<O> {
                 const TAG_NAME: &'static str = stringify!($name);
 
                 type Child = super::$children;
                 type Attribute = super::$attributes;
+                type Output = O;
 
                 fn attributes(&self) -> &Vec<Box<super::$attributes>> {
                     &self.attributes
@@ -50,6 +87,166 @@ macro_rules! make_tag {
                 fn children(&self) -> &Vec<Box<super::$children>> {
                     &self.children
                 }
+
+                fn events(&self) -> &crate::events::Events<O> {
+                    &self.events
+                }
+            }
+
+            impl<O: super::OutputType> std::fmt::Display for Element<O>
+            where
+                Self: super::Base,
+            {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    <Self as super::Base>::render(self, f)
+                }
+            }
+        }
+    };
+}
+
+/// Declare a new void tag module and implements it as a self-closing,
+/// childless tag. Void elements (`br`, `img`, ...) are the only tags
+/// allowed to use the self-closing form, and must never receive children,
+/// which is enforced by setting their `Child` type to the uninhabited
+/// `Empty` trait.
+macro_rules! make_void_tag {
+    ($name:ident, $attributes:ident) => {
+        /// The module for the tag, its name is the one you use with the `node!` macro.
+        pub mod $name {
+            /// The element representation
+            pub struct Element<O: super::OutputType = String> {
+                pub children: Vec<Box<super::Empty>>,
+                pub attributes: Vec<Box<super::$attributes>>,
+                pub events: crate::events::Events<O>,
+            }
+
+            impl<O: super::OutputType> super::Tag for Element<O> {
+                const TAG_NAME: &'static str = stringify!($name);
+                const VOID: bool = true;
+
+                type Child = super::Empty;
+                type Attribute = super::$attributes;
+                type Output = O;
+
+                fn attributes(&self) -> &Vec<Box<super::$attributes>> {
+                    &self.attributes
+                }
+
+                fn children(&self) -> &Vec<Box<super::Empty>> {
+                    &self.children
+                }
+
+                fn events(&self) -> &crate::events::Events<O> {
+                    &self.events
+                }
+            }
+
+            impl<O: super::OutputType> std::fmt::Display for Element<O>
+            where
+                Self: super::Base,
+            {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    <Self as super::Base>::render(self, f)
+                }
+            }
+        }
+    };
+}
+
+/// Declare a tag module whose children are a fixed, ordered set of required
+/// named children instead of a free-form `children` vec. Used for structural
+/// elements whose content model is an exact sequence, like `html` (`head`
+/// then `body`) or `head` (`title`). Each `$field` becomes a public struct
+/// field of the corresponding `$child` element type, and must be supplied
+/// when the element is built.
+///
+/// A trailing `$open_field: dyn $open_trait` adds one more field, a
+/// `Vec<Box<dyn $open_trait>>`, for content that isn't a fixed one-of-each
+/// sequence but an open list of anything implementing a content-model
+/// trait, like `head`'s metadata elements (`meta`, `link`, `style`, ...).
+macro_rules! make_structural_tag {
+    ($name:ident, $attributes:ident, [ $( $field:ident : $child:ident ),+ ]) => {
+        make_structural_tag!(@element $name, $attributes, [ $( $field : $child ),+ ], []);
+    };
+    ($name:ident, $attributes:ident, [ $( $field:ident : $child:ident ),+ ], $open_field:ident : dyn $open_trait:ident) => {
+        make_structural_tag!(@element $name, $attributes, [ $( $field : $child ),+ ], [ $open_field : $open_trait ]);
+    };
+    (@element $name:ident, $attributes:ident, [ $( $field:ident : $child:ident ),+ ], []) => {
+        /// The module for the tag, its name is the one you use with the `node!` macro.
+        pub mod $name {
+            /// The element representation
+            pub struct Element<O: super::OutputType = String> {
+                $( pub $field: Box<super::$child::Element<O>>, )+
+                pub attributes: Vec<Box<super::$attributes>>,
+                pub events: crate::events::Events<O>,
+            }
+
+            impl<O: super::OutputType> Element<O> {
+                /// The tag name as it will be shown in HTML
+                pub const TAG_NAME: &'static str = stringify!($name);
+            }
+
+            impl<O: super::OutputType> super::Base for Element<O> {
+                fn render(&self, f: &mut dyn std::fmt::Write) -> std::fmt::Result {
+                    use crate::attributes::Base as AttrBase;
+
+                    write!(f, "<{}", Self::TAG_NAME)?;
+                    for attribute in &self.attributes {
+                        write!(f, " ")?;
+                        AttrBase::render(attribute.as_ref(), f)?;
+                    }
+                    write!(f, ">")?;
+                    $( self.$field.render(f)?; )+
+                    write!(f, "</{}>", Self::TAG_NAME)
+                }
+            }
+
+            impl<O: super::OutputType> std::fmt::Display for Element<O> {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    <Self as super::Base>::render(self, f)
+                }
+            }
+        }
+    };
+    (@element $name:ident, $attributes:ident, [ $( $field:ident : $child:ident ),+ ], [ $open_field:ident : $open_trait:ident ]) => {
+        /// The module for the tag, its name is the one you use with the `node!` macro.
+        pub mod $name {
+            /// The element representation
+            pub struct Element<O: super::OutputType = String> {
+                $( pub $field: Box<super::$child::Element<O>>, )+
+                pub $open_field: Vec<Box<dyn super::$open_trait>>,
+                pub attributes: Vec<Box<super::$attributes>>,
+                pub events: crate::events::Events<O>,
+            }
+
+            impl<O: super::OutputType> Element<O> {
+                /// The tag name as it will be shown in HTML
+                pub const TAG_NAME: &'static str = stringify!($name);
+            }
+
+            impl<O: super::OutputType> super::Base for Element<O> {
+                fn render(&self, f: &mut dyn std::fmt::Write) -> std::fmt::Result {
+                    use crate::attributes::Base as AttrBase;
+
+                    write!(f, "<{}", Self::TAG_NAME)?;
+                    for attribute in &self.attributes {
+                        write!(f, " ")?;
+                        AttrBase::render(attribute.as_ref(), f)?;
+                    }
+                    write!(f, ">")?;
+                    for child in &self.$open_field {
+                        child.render(f)?;
+                    }
+                    $( self.$field.render(f)?; )+
+                    write!(f, "</{}>", Self::TAG_NAME)
+                }
+            }
+
+            impl<O: super::OutputType> std::fmt::Display for Element<O> {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    <Self as super::Base>::render(self, f)
+                }
             }
         }
     };
@@ -61,55 +258,509 @@ where
     T::Child: Base,
     T::Attribute: crate::attributes::Base,
 {
-    fn to_string(&self) -> String {
-        use crate::attributes::Base;
+    fn render(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        use crate::attributes::Base as AttrBase;
+
+        write!(f, "<{}", Self::TAG_NAME)?;
+        for attribute in self.attributes() {
+            write!(f, " ")?;
+            AttrBase::render(attribute.as_ref(), f)?;
+        }
+
+        if Self::VOID {
+            return write!(f, " />");
+        }
 
         let children = self.children();
-        let attributes = self
-            .attributes()
-            .iter()
-            .map(|ref attribute| format!(" {}", attribute.to_string()))
-            .collect::<String>();
-
-        if children.len() == 0 {
-            format!("<{}{} />", Self::TAG_NAME, attributes)
+        if children.is_empty() {
+            write!(f, "></{}>", Self::TAG_NAME)
         } else {
-            format!(
-                "<{0}{2}>{1}</{0}>",
-                Self::TAG_NAME,
-                children
-                    .iter()
-                    .map(|ref child| child.to_string())
-                    .collect::<String>(),
-                attributes
-            )
+            write!(f, ">")?;
+            for child in children {
+                child.render(f)?;
+            }
+            write!(f, "</{}>", Self::TAG_NAME)
         }
     }
 }
 
 /// Pseudo group indicating an element should never have children.
-/// No tags should implement this interface.
+/// No tags should implement this interface, so it can never be satisfied
+/// by an actual child, making it impossible to give a void element children.
 pub trait Empty: Base {}
 
-/// HTML flow elements.
-pub trait FlowElement: Base {}
+/// The HTML5 content categories, used to constrain what a tag accepts as
+/// children. A tag declares its `Child` type as one of these traits, and
+/// only the elements that implement it can be passed as children, so
+/// malformed document structure (e.g. a `<div>` inside a `<title>`) is a
+/// compile error.
+///
+/// Metadata content: information about the document that isn't content
+/// itself (`title`, `style`, ...).
+pub trait MetadataContent: Base {}
+
+/// Flow content: most elements usable in the body of a document or
+/// application.
+pub trait FlowContent: Base {}
+
+/// Phrasing content: the text and text-level markup of a document. A
+/// subset of flow content.
+pub trait PhrasingContent: FlowContent {}
+
+/// Embedded content: content that imports another resource (`img`,
+/// `embed`, `picture`'s `source`/`img` children, ...). A subset of
+/// phrasing content, and the `Child` type of `picture`.
+pub trait EmbeddedContent: PhrasingContent {}
+
+/// Text content: plain, unmarked-up text, as used by elements that accept
+/// no markup at all (`title`, `style`).
+pub trait TextContent: Base {}
+
+/// List item content: the only valid children of `ul`, `ol` and `menu`.
+pub trait ListItemContent: Base {}
+
+/// Description list content: the only valid children of `dl` (`dt`, `dd`).
+pub trait DescriptionListContent: Base {}
+
+/// Table section content: the only valid direct children of `table`
+/// (`caption`, `colgroup`, `thead`, `tbody`, `tfoot`, `tr`).
+pub trait TableSectionContent: Base {}
+
+/// Table row content: the only valid children of `thead`, `tbody` and
+/// `tfoot` (`tr`).
+pub trait TableRowContent: Base {}
+
+/// Table cell content: the only valid children of `tr` (`td`, `th`).
+pub trait TableCellContent: Base {}
+
+/// Column content: the only valid children of `colgroup` (`col`).
+pub trait ColumnContent: Base {}
+
+/// Option content: the only valid children of `optgroup` (`option`).
+pub trait OptionContent: Base {}
+
+/// Select content: the valid children of `select` and `datalist`
+/// (`option`, `optgroup`).
+pub trait SelectContent: Base {}
 
 use super::attributes::DefaultAttribute;
 
-make_tag!(span, FlowElement, DefaultAttribute);
-make_tag!(div, FlowElement, DefaultAttribute);
-make_tag!(html, FlowElement, DefaultAttribute);
+make_tag!(span, PhrasingContent, DefaultAttribute);
+make_tag!(div, FlowContent, DefaultAttribute);
+make_tag!(p, PhrasingContent, DefaultAttribute);
+make_tag!(a, PhrasingContent, DefaultAttribute);
+make_tag!(strong, PhrasingContent, DefaultAttribute);
+make_tag!(em, PhrasingContent, DefaultAttribute);
+make_tag!(b, PhrasingContent, DefaultAttribute);
+make_tag!(i, PhrasingContent, DefaultAttribute);
+make_tag!(button, PhrasingContent, DefaultAttribute);
+make_tag!(ul, ListItemContent, DefaultAttribute);
+make_tag!(ol, ListItemContent, DefaultAttribute);
+make_tag!(li, FlowContent, DefaultAttribute);
+make_tag!(article, FlowContent, DefaultAttribute);
+make_tag!(aside, FlowContent, DefaultAttribute);
+make_tag!(nav, FlowContent, DefaultAttribute);
+make_tag!(section, FlowContent, DefaultAttribute);
+make_tag!(header, FlowContent, DefaultAttribute);
+make_tag!(footer, FlowContent, DefaultAttribute);
+make_tag!(body, FlowContent, DefaultAttribute);
+make_tag!(title, TextContent, DefaultAttribute);
+make_tag!(style, TextContent, DefaultAttribute);
+
+impl FlowContent for div::Element {}
+
+impl FlowContent for span::Element {}
+impl PhrasingContent for span::Element {}
+
+impl FlowContent for p::Element {}
+impl PhrasingContent for p::Element {}
 
-impl FlowElement for span::Element {}
-impl FlowElement for div::Element {}
+impl FlowContent for a::Element {}
+impl PhrasingContent for a::Element {}
 
-/// Represents a text node alone. Converted to its contents when transformed into string.
+impl FlowContent for strong::Element {}
+impl PhrasingContent for strong::Element {}
+
+impl FlowContent for em::Element {}
+impl PhrasingContent for em::Element {}
+
+impl FlowContent for b::Element {}
+impl PhrasingContent for b::Element {}
+
+impl FlowContent for i::Element {}
+impl PhrasingContent for i::Element {}
+
+impl FlowContent for button::Element {}
+impl PhrasingContent for button::Element {}
+
+impl FlowContent for ul::Element {}
+impl FlowContent for ol::Element {}
+impl ListItemContent for li::Element {}
+
+impl FlowContent for article::Element {}
+impl FlowContent for aside::Element {}
+impl FlowContent for nav::Element {}
+impl FlowContent for section::Element {}
+
+impl FlowContent for header::Element {}
+impl FlowContent for footer::Element {}
+
+impl MetadataContent for title::Element {}
+impl MetadataContent for style::Element {}
+
+make_structural_tag!(head, DefaultAttribute, [title: title], metadata: dyn MetadataContent);
+make_structural_tag!(html, DefaultAttribute, [head: head, body: body]);
+
+make_void_tag!(area, DefaultAttribute);
+make_void_tag!(base, DefaultAttribute);
+make_void_tag!(br, DefaultAttribute);
+make_void_tag!(col, DefaultAttribute);
+make_void_tag!(embed, DefaultAttribute);
+make_void_tag!(hr, DefaultAttribute);
+make_void_tag!(img, DefaultAttribute);
+make_void_tag!(input, DefaultAttribute);
+make_void_tag!(link, DefaultAttribute);
+make_void_tag!(meta, DefaultAttribute);
+make_void_tag!(source, DefaultAttribute);
+make_void_tag!(track, DefaultAttribute);
+make_void_tag!(wbr, DefaultAttribute);
+
+impl FlowContent for area::Element {}
+impl PhrasingContent for area::Element {}
+
+impl MetadataContent for base::Element {}
+
+impl FlowContent for br::Element {}
+impl PhrasingContent for br::Element {}
+
+impl FlowContent for col::Element {}
+impl ColumnContent for col::Element {}
+
+impl FlowContent for embed::Element {}
+impl PhrasingContent for embed::Element {}
+impl EmbeddedContent for embed::Element {}
+
+impl FlowContent for hr::Element {}
+
+impl FlowContent for img::Element {}
+impl PhrasingContent for img::Element {}
+impl EmbeddedContent for img::Element {}
+
+impl FlowContent for input::Element {}
+impl PhrasingContent for input::Element {}
+
+impl MetadataContent for link::Element {}
+impl MetadataContent for meta::Element {}
+
+impl FlowContent for source::Element {}
+impl PhrasingContent for source::Element {}
+impl EmbeddedContent for source::Element {}
+
+impl FlowContent for track::Element {}
+
+impl FlowContent for wbr::Element {}
+impl PhrasingContent for wbr::Element {}
+
+make_tag!(h1, PhrasingContent, DefaultAttribute);
+make_tag!(h2, PhrasingContent, DefaultAttribute);
+make_tag!(h3, PhrasingContent, DefaultAttribute);
+make_tag!(h4, PhrasingContent, DefaultAttribute);
+make_tag!(h5, PhrasingContent, DefaultAttribute);
+make_tag!(h6, PhrasingContent, DefaultAttribute);
+
+impl FlowContent for h1::Element {}
+impl FlowContent for h2::Element {}
+impl FlowContent for h3::Element {}
+impl FlowContent for h4::Element {}
+impl FlowContent for h5::Element {}
+impl FlowContent for h6::Element {}
+
+make_tag!(blockquote, FlowContent, DefaultAttribute);
+make_tag!(pre, PhrasingContent, DefaultAttribute);
+make_tag!(q, PhrasingContent, DefaultAttribute);
+make_tag!(cite, PhrasingContent, DefaultAttribute);
+make_tag!(small, PhrasingContent, DefaultAttribute);
+make_tag!(mark, PhrasingContent, DefaultAttribute);
+make_tag!(sub, PhrasingContent, DefaultAttribute);
+make_tag!(sup, PhrasingContent, DefaultAttribute);
+make_tag!(abbr, PhrasingContent, DefaultAttribute);
+make_tag!(dfn, PhrasingContent, DefaultAttribute);
+make_tag!(time, PhrasingContent, DefaultAttribute);
+make_tag!(data, PhrasingContent, DefaultAttribute);
+make_tag!(code, PhrasingContent, DefaultAttribute);
+make_tag!(var, PhrasingContent, DefaultAttribute);
+make_tag!(samp, PhrasingContent, DefaultAttribute);
+make_tag!(kbd, PhrasingContent, DefaultAttribute);
+make_tag!(u, PhrasingContent, DefaultAttribute);
+make_tag!(bdi, PhrasingContent, DefaultAttribute);
+make_tag!(bdo, PhrasingContent, DefaultAttribute);
+make_tag!(ins, FlowContent, DefaultAttribute);
+make_tag!(del, FlowContent, DefaultAttribute);
+
+impl FlowContent for blockquote::Element {}
+
+impl FlowContent for pre::Element {}
+impl PhrasingContent for pre::Element {}
+
+impl FlowContent for q::Element {}
+impl PhrasingContent for q::Element {}
+
+impl FlowContent for cite::Element {}
+impl PhrasingContent for cite::Element {}
+
+impl FlowContent for small::Element {}
+impl PhrasingContent for small::Element {}
+
+impl FlowContent for mark::Element {}
+impl PhrasingContent for mark::Element {}
+
+impl FlowContent for sub::Element {}
+impl PhrasingContent for sub::Element {}
+
+impl FlowContent for sup::Element {}
+impl PhrasingContent for sup::Element {}
+
+impl FlowContent for abbr::Element {}
+impl PhrasingContent for abbr::Element {}
+
+impl FlowContent for dfn::Element {}
+impl PhrasingContent for dfn::Element {}
+
+impl FlowContent for time::Element {}
+impl PhrasingContent for time::Element {}
+
+impl FlowContent for data::Element {}
+impl PhrasingContent for data::Element {}
+
+impl FlowContent for code::Element {}
+impl PhrasingContent for code::Element {}
+
+impl FlowContent for var::Element {}
+impl PhrasingContent for var::Element {}
+
+impl FlowContent for samp::Element {}
+impl PhrasingContent for samp::Element {}
+
+impl FlowContent for kbd::Element {}
+impl PhrasingContent for kbd::Element {}
+
+impl FlowContent for u::Element {}
+impl PhrasingContent for u::Element {}
+
+impl FlowContent for bdi::Element {}
+impl PhrasingContent for bdi::Element {}
+
+impl FlowContent for bdo::Element {}
+impl PhrasingContent for bdo::Element {}
+
+impl FlowContent for ins::Element {}
+impl PhrasingContent for ins::Element {}
+
+impl FlowContent for del::Element {}
+impl PhrasingContent for del::Element {}
+
+make_tag!(figure, FlowContent, DefaultAttribute);
+make_tag!(figcaption, FlowContent, DefaultAttribute);
+make_tag!(main, FlowContent, DefaultAttribute);
+make_tag!(address, FlowContent, DefaultAttribute);
+make_tag!(hgroup, FlowContent, DefaultAttribute);
+make_tag!(menu, ListItemContent, DefaultAttribute);
+make_tag!(dl, DescriptionListContent, DefaultAttribute);
+make_tag!(dt, FlowContent, DefaultAttribute);
+make_tag!(dd, FlowContent, DefaultAttribute);
+
+impl FlowContent for figure::Element {}
+impl FlowContent for figcaption::Element {}
+impl FlowContent for main::Element {}
+impl FlowContent for address::Element {}
+impl FlowContent for hgroup::Element {}
+impl FlowContent for menu::Element {}
+impl FlowContent for dl::Element {}
+impl FlowContent for dt::Element {}
+impl FlowContent for dd::Element {}
+
+impl DescriptionListContent for dt::Element {}
+impl DescriptionListContent for dd::Element {}
+
+make_tag!(details, FlowContent, DefaultAttribute);
+make_tag!(summary, PhrasingContent, DefaultAttribute);
+make_tag!(dialog, FlowContent, DefaultAttribute);
+
+impl FlowContent for details::Element {}
+
+impl FlowContent for summary::Element {}
+impl PhrasingContent for summary::Element {}
+
+impl FlowContent for dialog::Element {}
+
+make_tag!(script, TextContent, DefaultAttribute);
+make_tag!(noscript, FlowContent, DefaultAttribute);
+make_tag!(template, FlowContent, DefaultAttribute);
+make_tag!(canvas, FlowContent, DefaultAttribute);
+
+impl MetadataContent for script::Element {}
+impl FlowContent for script::Element {}
+
+impl MetadataContent for noscript::Element {}
+impl FlowContent for noscript::Element {}
+
+impl MetadataContent for template::Element {}
+impl FlowContent for template::Element {}
+
+impl FlowContent for canvas::Element {}
+impl PhrasingContent for canvas::Element {}
+impl EmbeddedContent for canvas::Element {}
+
+make_tag!(audio, FlowContent, DefaultAttribute);
+make_tag!(video, FlowContent, DefaultAttribute);
+// `iframe` is not a void element (it has a closing tag, rendered through
+// `make_tag!`'s regular open/close `Base` impl, not `make_void_tag!`'s
+// self-closing one) but its content is fallback content for browsers that
+// can't display frames, which this crate doesn't support rendering yet.
+// `Empty` is reused here only to reject children at the type level until
+// fallback content is modeled; it's not a void tag.
+make_tag!(iframe, Empty, DefaultAttribute);
+make_tag!(object, FlowContent, DefaultAttribute);
+make_tag!(map, FlowContent, DefaultAttribute);
+make_tag!(picture, EmbeddedContent, DefaultAttribute);
+
+impl FlowContent for audio::Element {}
+impl PhrasingContent for audio::Element {}
+impl EmbeddedContent for audio::Element {}
+
+impl FlowContent for video::Element {}
+impl PhrasingContent for video::Element {}
+impl EmbeddedContent for video::Element {}
+
+impl FlowContent for iframe::Element {}
+impl PhrasingContent for iframe::Element {}
+impl EmbeddedContent for iframe::Element {}
+
+impl FlowContent for object::Element {}
+impl PhrasingContent for object::Element {}
+impl EmbeddedContent for object::Element {}
+
+impl FlowContent for map::Element {}
+
+impl FlowContent for picture::Element {}
+impl PhrasingContent for picture::Element {}
+impl EmbeddedContent for picture::Element {}
+
+make_tag!(table, TableSectionContent, DefaultAttribute);
+make_tag!(caption, FlowContent, DefaultAttribute);
+make_tag!(colgroup, ColumnContent, DefaultAttribute);
+make_tag!(thead, TableRowContent, DefaultAttribute);
+make_tag!(tbody, TableRowContent, DefaultAttribute);
+make_tag!(tfoot, TableRowContent, DefaultAttribute);
+make_tag!(tr, TableCellContent, DefaultAttribute);
+make_tag!(td, FlowContent, DefaultAttribute);
+make_tag!(th, FlowContent, DefaultAttribute);
+
+impl FlowContent for table::Element {}
+
+impl TableSectionContent for caption::Element {}
+impl TableSectionContent for colgroup::Element {}
+impl TableSectionContent for thead::Element {}
+impl TableSectionContent for tbody::Element {}
+impl TableSectionContent for tfoot::Element {}
+impl TableSectionContent for tr::Element {}
+
+impl TableRowContent for tr::Element {}
+
+impl TableCellContent for td::Element {}
+impl TableCellContent for th::Element {}
+
+make_tag!(form, FlowContent, DefaultAttribute);
+make_tag!(label, PhrasingContent, DefaultAttribute);
+make_tag!(select, SelectContent, DefaultAttribute);
+make_tag!(datalist, SelectContent, DefaultAttribute);
+make_tag!(optgroup, OptionContent, DefaultAttribute);
+make_tag!(option, TextContent, DefaultAttribute);
+make_tag!(textarea, TextContent, DefaultAttribute);
+make_tag!(output, PhrasingContent, DefaultAttribute);
+make_tag!(progress, PhrasingContent, DefaultAttribute);
+make_tag!(meter, PhrasingContent, DefaultAttribute);
+make_tag!(fieldset, FlowContent, DefaultAttribute);
+make_tag!(legend, PhrasingContent, DefaultAttribute);
+
+impl FlowContent for form::Element {}
+
+impl FlowContent for label::Element {}
+impl PhrasingContent for label::Element {}
+
+impl FlowContent for select::Element {}
+impl PhrasingContent for select::Element {}
+
+impl FlowContent for datalist::Element {}
+impl PhrasingContent for datalist::Element {}
+
+impl SelectContent for optgroup::Element {}
+impl OptionContent for option::Element {}
+impl SelectContent for option::Element {}
+
+impl FlowContent for textarea::Element {}
+impl PhrasingContent for textarea::Element {}
+
+impl FlowContent for output::Element {}
+impl PhrasingContent for output::Element {}
+
+impl FlowContent for progress::Element {}
+impl PhrasingContent for progress::Element {}
+
+impl FlowContent for meter::Element {}
+impl PhrasingContent for meter::Element {}
+
+impl FlowContent for fieldset::Element {}
+
+impl FlowContent for legend::Element {}
+impl PhrasingContent for legend::Element {}
+
+/// Escapes the characters that would otherwise be interpreted as markup
+/// when appearing in element text content.
+fn escape(input: &str) -> String {
+    input
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Represents a text node alone. Escaped, then converted to its contents
+/// when transformed into string.
 pub struct Text(pub String);
 
 impl Base for Text {
-    fn to_string(&self) -> String {
-        self.0.clone()
+    fn render(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        write!(f, "{}", escape(&self.0))
+    }
+}
+
+impl fmt::Display for Text {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Base::render(self, f)
+    }
+}
+
+impl FlowContent for Text {}
+impl PhrasingContent for Text {}
+impl TextContent for Text {}
+
+/// A text node whose contents are inserted verbatim, without escaping.
+/// Use this for markup that is already escaped or otherwise trusted,
+/// such as output coming from another `Base` implementation.
+pub struct RawText(pub String);
+
+impl Base for RawText {
+    fn render(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl fmt::Display for RawText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Base::render(self, f)
     }
 }
 
-impl FlowElement for Text {}
+impl FlowContent for RawText {}
+impl PhrasingContent for RawText {}
+impl TextContent for RawText {}